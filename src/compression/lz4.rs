@@ -0,0 +1,147 @@
+use crate::{Compressor, Expander, StreamlinerError};
+use std::io::{Read, Write};
+
+/// Lowest `level` at which LZ4F's encoder actually engages HC mode; below
+/// this it silently behaves like fast mode despite accepting the level.
+const LZ4HC_CLEVEL_MIN: u32 = 3;
+
+/// Selects between LZ4's default fast mode and its high-compression (HC)
+/// mode. Both produce standard LZ4 frames decoded by the same
+/// [`Lz4Expander`], so callers can switch modes per write without changing
+/// how reads are done.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lz4Mode {
+    /// Default fast mode; lowest latency, the weakest ratio of the three backends.
+    Fast,
+    /// High-compression mode at the given level (3-12, see
+    /// [`Lz4Compressor::high_compression`]). Recovers much of the ratio
+    /// zlib/zstd would give, at a slower encode.
+    HighCompression(u32),
+}
+
+/// LZ4 frame compressor. Decompression is dramatically faster than
+/// zlib/zstd, which matters when a serving path expands a cached context on
+/// every request; HC mode trades encode latency for ratio on the rarer
+/// write path.
+pub struct Lz4Compressor {
+    mode: Lz4Mode,
+}
+
+impl Lz4Compressor {
+    /// Creates a compressor using LZ4's default fast mode.
+    pub fn fast() -> Self {
+        Self { mode: Lz4Mode::Fast }
+    }
+
+    /// Creates a compressor using LZ4 HC mode at `level` (0-12). The
+    /// underlying LZ4F library only actually switches into HC encoding once
+    /// `level` reaches `LZ4HC_CLEVEL_MIN`; below that it silently falls back
+    /// to fast mode, so levels are clamped up to that floor here.
+    pub fn high_compression(level: u32) -> Self {
+        Self {
+            mode: Lz4Mode::HighCompression(level.max(LZ4HC_CLEVEL_MIN)),
+        }
+    }
+}
+
+impl Default for Lz4Compressor {
+    fn default() -> Self {
+        Self::fast()
+    }
+}
+
+#[async_trait::async_trait]
+impl Compressor for Lz4Compressor {
+    async fn compress(&'async_trait self, context: &'async_trait str) -> Result<Vec<u8>, StreamlinerError> {
+        let level = match self.mode {
+            Lz4Mode::Fast => 0,
+            Lz4Mode::HighCompression(level) => level,
+        };
+        let mut encoder = lz4::EncoderBuilder::new()
+            .level(level)
+            .build(Vec::new())
+            .map_err(StreamlinerError::CompressionError)?;
+        encoder.write_all(context.as_bytes())?;
+        let (output, result) = encoder.finish();
+        result.map_err(StreamlinerError::CompressionError)?;
+        Ok(output)
+    }
+
+    fn codec_tag(&self) -> String {
+        match self.mode {
+            Lz4Mode::Fast => "lz4:v1:mode=fast".to_string(),
+            Lz4Mode::HighCompression(level) => format!("lz4:v1:mode=hc,level={}", level),
+        }
+    }
+}
+
+/// LZ4 frame expander. Fast- and HC-produced frames share the same decode
+/// path, so a single expander handles both transparently.
+pub struct Lz4Expander;
+
+#[async_trait::async_trait]
+impl Expander for Lz4Expander {
+    async fn expand(&'async_trait self, compressed: &'async_trait [u8]) -> Result<String, StreamlinerError> {
+        let mut decoder = lz4::Decoder::new(compressed)
+            .map_err(|e| StreamlinerError::ExpansionError(e.to_string()))?;
+        let mut output = String::new();
+        decoder
+            .read_to_string(&mut output)
+            .map_err(|e| StreamlinerError::ExpansionError(e.to_string()))?;
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::test;
+
+    #[test]
+    async fn test_lz4_fast_roundtrip() {
+        let compressor = Lz4Compressor::fast();
+        let expander = Lz4Expander;
+        let original = "test context";
+
+        let compressed = compressor.compress(original).await.unwrap();
+        let expanded = expander.expand(&compressed).await.unwrap();
+
+        assert_eq!(original, expanded);
+    }
+
+    #[test]
+    async fn test_lz4_high_compression_roundtrip() {
+        let compressor = Lz4Compressor::high_compression(9);
+        let expander = Lz4Expander;
+        let original = "test context repeated test context repeated test context";
+
+        let compressed = compressor.compress(original).await.unwrap();
+        let expanded = expander.expand(&compressed).await.unwrap();
+
+        assert_eq!(original, expanded);
+    }
+
+    #[test]
+    async fn test_lz4_expander_handles_both_modes() {
+        let expander = Lz4Expander;
+        let original = "shared decode path for fast and HC frames";
+
+        let fast = Lz4Compressor::fast().compress(original).await.unwrap();
+        let hc = Lz4Compressor::high_compression(6).compress(original).await.unwrap();
+
+        assert_eq!(expander.expand(&fast).await.unwrap(), original);
+        assert_eq!(expander.expand(&hc).await.unwrap(), original);
+    }
+
+    #[test]
+    async fn test_high_compression_clamps_level_below_hc_floor() {
+        assert_eq!(
+            Lz4Compressor::high_compression(0).mode,
+            Lz4Mode::HighCompression(LZ4HC_CLEVEL_MIN)
+        );
+        assert_eq!(
+            Lz4Compressor::high_compression(LZ4HC_CLEVEL_MIN + 1).mode,
+            Lz4Mode::HighCompression(LZ4HC_CLEVEL_MIN + 1)
+        );
+    }
+}
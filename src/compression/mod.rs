@@ -0,0 +1,12 @@
+//! Compression backends implementing the [`Compressor`](crate::Compressor)/
+//! [`Expander`](crate::Expander) traits.
+
+mod lz4;
+mod zlib;
+mod zstd;
+mod zstd_dict;
+
+pub use lz4::{Lz4Compressor, Lz4Expander, Lz4Mode};
+pub use zlib::{ZlibCompressor, ZlibExpander};
+pub use zstd::{ZstdCompressor, ZstdExpander};
+pub use zstd_dict::{train_dictionary, ZstdDictCompressor, ZstdDictExpander};
@@ -0,0 +1,176 @@
+use crate::{Compressor, Expander, StreamlinerError};
+use async_compression::tokio::bufread::ZlibDecoder;
+use async_compression::tokio::write::ZlibEncoder;
+use std::io::Cursor;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt, BufReader, ReadBuf};
+
+/// Size of the in-memory pipe used to stream compressed bytes out of
+/// [`ZlibCompressor::compress_stream`] as they're produced.
+const STREAM_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Wraps a stream so that if the background task feeding it fails partway
+/// through, the failure surfaces as an `Err` from `poll_read` at the point
+/// the stream ends, instead of silently looking like a clean EOF.
+struct FallibleStream<R> {
+    inner: R,
+    error: Arc<Mutex<Option<std::io::Error>>>,
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for FallibleStream<R> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let filled_before = buf.filled().len();
+        match Pin::new(&mut self.inner).poll_read(cx, buf) {
+            Poll::Ready(Ok(())) if buf.filled().len() == filled_before => {
+                match self.error.lock().unwrap().take() {
+                    Some(err) => Poll::Ready(Err(err)),
+                    None => Poll::Ready(Ok(())),
+                }
+            }
+            other => other,
+        }
+    }
+}
+
+/// Zlib-based compression implementation.
+///
+/// Built on `async-compression`'s Tokio adaptors so large contexts are
+/// streamed through rather than blocking the worker on synchronous flate2
+/// calls.
+pub struct ZlibCompressor;
+
+impl ZlibCompressor {
+    /// Compresses an async byte stream, yielding compressed bytes as they
+    /// become available so multi-megabyte contexts never need to be held
+    /// fully in memory. A failure reading `reader` or encoding mid-stream is
+    /// surfaced as an `io::Error` on the returned stream rather than a
+    /// silently truncated one.
+    pub fn compress_stream<R>(mut reader: R) -> impl AsyncRead + Unpin + Send + 'static
+    where
+        R: AsyncRead + Unpin + Send + 'static,
+    {
+        let (client, mut server) = tokio::io::duplex(STREAM_BUFFER_SIZE);
+        let error = Arc::new(Mutex::new(None));
+        let task_error = Arc::clone(&error);
+        tokio::spawn(async move {
+            let mut encoder = ZlibEncoder::new(&mut server);
+            let result: std::io::Result<()> = async {
+                tokio::io::copy(&mut reader, &mut encoder).await?;
+                encoder.shutdown().await
+            }
+            .await;
+            if let Err(err) = result {
+                *task_error.lock().unwrap() = Some(err);
+            }
+        });
+        FallibleStream {
+            inner: client,
+            error,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Compressor for ZlibCompressor {
+    async fn compress(&'async_trait self, context: &'async_trait str) -> Result<Vec<u8>, StreamlinerError> {
+        let mut stream = Self::compress_stream(Cursor::new(context.as_bytes().to_vec()));
+        let mut output = Vec::new();
+        stream.read_to_end(&mut output).await?;
+        Ok(output)
+    }
+
+    fn codec_tag(&self) -> String {
+        "zlib:v1".to_string()
+    }
+}
+
+/// Zlib-based expansion implementation.
+pub struct ZlibExpander;
+
+impl ZlibExpander {
+    /// Expands an async byte stream of zlib-compressed data into a stream of
+    /// decompressed bytes, without buffering the whole input or output.
+    pub fn expand_stream<R>(reader: R) -> impl AsyncRead + Unpin
+    where
+        R: AsyncRead + Unpin,
+    {
+        ZlibDecoder::new(BufReader::new(reader))
+    }
+}
+
+#[async_trait::async_trait]
+impl Expander for ZlibExpander {
+    async fn expand(&'async_trait self, compressed: &'async_trait [u8]) -> Result<String, StreamlinerError> {
+        let mut stream = Self::expand_stream(compressed);
+        let mut output = String::new();
+        stream
+            .read_to_string(&mut output)
+            .await
+            .map_err(|e| StreamlinerError::ExpansionError(e.to_string()))?;
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::test;
+
+    #[test]
+    async fn test_zlib_roundtrip() {
+        let compressor = ZlibCompressor;
+        let expander = ZlibExpander;
+        let original = "test context";
+
+        let compressed = compressor.compress(original).await.unwrap();
+        let expanded = expander.expand(&compressed).await.unwrap();
+
+        assert_eq!(original, expanded);
+    }
+
+    #[test]
+    async fn test_zlib_stream_roundtrip() {
+        let original = "streamed test context repeated many times for good measure";
+
+        let mut compressed = Vec::new();
+        ZlibCompressor::compress_stream(Cursor::new(original.as_bytes().to_vec()))
+            .read_to_end(&mut compressed)
+            .await
+            .unwrap();
+
+        let mut expanded = String::new();
+        ZlibExpander::expand_stream(compressed.as_slice())
+            .read_to_string(&mut expanded)
+            .await
+            .unwrap();
+
+        assert_eq!(original, expanded);
+    }
+
+    #[test]
+    async fn test_compress_stream_propagates_reader_error() {
+        struct FailingReader;
+
+        impl AsyncRead for FailingReader {
+            fn poll_read(
+                self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+                _buf: &mut ReadBuf<'_>,
+            ) -> Poll<std::io::Result<()>> {
+                Poll::Ready(Err(std::io::Error::other("simulated read failure")))
+            }
+        }
+
+        let mut stream = ZlibCompressor::compress_stream(FailingReader);
+        let mut output = Vec::new();
+        let result = stream.read_to_end(&mut output).await;
+
+        assert!(result.is_err());
+    }
+}
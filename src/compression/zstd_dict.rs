@@ -0,0 +1,176 @@
+use crate::{Compressor, Expander, MemoryModule, StreamlinerError};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use zstd::dict::{DecoderDictionary, EncoderDictionary};
+
+/// Trains a zstd dictionary from sample buffers via `ZDICT_trainFromBuffer`.
+///
+/// LLM context snippets (system prompts, tool schemas, few-shot examples)
+/// are individually small but share a huge amount of vocabulary; a trained
+/// dictionary lets each one skip the per-stream warm-up cost that would
+/// otherwise dominate on small inputs. `samples` should total well above
+/// `dict_size`, or training fails.
+pub fn train_dictionary(samples: &[&str], dict_size: usize) -> Result<Vec<u8>, StreamlinerError> {
+    let buffers: Vec<&[u8]> = samples.iter().map(|s| s.as_bytes()).collect();
+    zstd::dict::from_samples(&buffers, dict_size)
+        .map_err(|e| StreamlinerError::DictionaryTrainingError(e.to_string()))
+}
+
+/// Computes a short, stable identifier for a dictionary blob, used to tag
+/// `MemoryModule::metadata` so an expander can refuse to decompress with the
+/// wrong dictionary.
+fn dictionary_id(dictionary: &[u8]) -> String {
+    let mut hasher = DefaultHasher::new();
+    dictionary.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Zstd compressor built from a trained dictionary, reused across every
+/// `compress` call to avoid the per-stream warm-up cost on small,
+/// vocabulary-heavy context snippets.
+pub struct ZstdDictCompressor<'a> {
+    dict: EncoderDictionary<'a>,
+    dict_id: String,
+    level: i32,
+}
+
+impl<'a> ZstdDictCompressor<'a> {
+    /// Builds a compressor from a trained dictionary at zstd's default level.
+    pub fn new(dictionary: &'a [u8]) -> Self {
+        Self::with_level(dictionary, zstd::DEFAULT_COMPRESSION_LEVEL)
+    }
+
+    /// Builds a compressor from a trained dictionary at an explicit level.
+    pub fn with_level(dictionary: &'a [u8], level: i32) -> Self {
+        Self {
+            dict: EncoderDictionary::copy(dictionary, level),
+            dict_id: dictionary_id(dictionary),
+            level,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> Compressor for ZstdDictCompressor<'a> {
+    async fn compress(&'async_trait self, context: &'async_trait str) -> Result<Vec<u8>, StreamlinerError> {
+        let mut encoder = zstd::stream::Encoder::with_prepared_dictionary(Vec::new(), &self.dict)
+            .map_err(StreamlinerError::CompressionError)?;
+        std::io::Write::write_all(&mut encoder, context.as_bytes())?;
+        encoder.finish().map_err(StreamlinerError::CompressionError)
+    }
+
+    fn codec_tag(&self) -> String {
+        format!("zstd-dict:v1:level={},dict={}", self.level, self.dict_id)
+    }
+}
+
+/// Zstd expander paired with a pre-trained dictionary; use [`ZstdDictExpander::expand_module`]
+/// to refuse expanding data that was compressed with a different dictionary.
+pub struct ZstdDictExpander<'a> {
+    dict: DecoderDictionary<'a>,
+    dict_id: String,
+}
+
+impl<'a> ZstdDictExpander<'a> {
+    /// Builds an expander from a trained dictionary.
+    pub fn new(dictionary: &'a [u8]) -> Self {
+        Self {
+            dict: DecoderDictionary::copy(dictionary),
+            dict_id: dictionary_id(dictionary),
+        }
+    }
+
+    /// Expands `module`, refusing to proceed if it was compressed with a
+    /// different dictionary than the one this expander was built with.
+    pub async fn expand_module(&self, module: &MemoryModule) -> Result<String, StreamlinerError> {
+        let expected = format!("dict={}", self.dict_id);
+        if !module.metadata().contains(expected.as_str()) {
+            return Err(StreamlinerError::ExpansionError(format!(
+                "dictionary mismatch: module metadata {:?} does not reference dictionary {}",
+                module.metadata(),
+                self.dict_id
+            )));
+        }
+        module.expand(self).await
+    }
+}
+
+#[async_trait::async_trait]
+impl<'a> Expander for ZstdDictExpander<'a> {
+    async fn expand(&'async_trait self, compressed: &'async_trait [u8]) -> Result<String, StreamlinerError> {
+        let mut decoder = zstd::stream::Decoder::with_prepared_dictionary(compressed, &self.dict)
+            .map_err(|e| StreamlinerError::ExpansionError(e.to_string()))?;
+        let mut output = String::new();
+        std::io::Read::read_to_string(&mut decoder, &mut output)
+            .map_err(|e| StreamlinerError::ExpansionError(e.to_string()))?;
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::test;
+
+    // `zstd::dict::from_samples` needs a corpus roughly two orders of
+    // magnitude larger than `dict_size` or training fails with
+    // "Destination buffer is too small"; a handful of short strings isn't
+    // close. Repeat a small set of templates with an index suffix to build
+    // a corpus well past that threshold without hand-writing kilobytes of
+    // text.
+    fn generate_samples(templates: &[&str], count: usize) -> Vec<String> {
+        (0..count)
+            .map(|i| format!("{} (sample #{i})", templates[i % templates.len()]))
+            .collect()
+    }
+
+    fn sample_refs(samples: &[String]) -> Vec<&str> {
+        samples.iter().map(String::as_str).collect()
+    }
+
+    const TEMPLATES: &[&str] = &[
+        "You are a helpful assistant. Use the provided tools when appropriate.",
+        "You are a helpful assistant. Answer concisely and cite your sources.",
+        "You are a helpful assistant. Follow the system prompt exactly as written.",
+        "You are a helpful assistant. Prefer short, direct responses to the user.",
+    ];
+
+    #[test]
+    async fn test_dictionary_roundtrip() {
+        let samples = generate_samples(TEMPLATES, 500);
+        let dict = train_dictionary(&sample_refs(&samples), 512).unwrap();
+        let compressor = ZstdDictCompressor::new(&dict);
+        let expander = ZstdDictExpander::new(&dict);
+        let original = &samples[0];
+
+        let module = MemoryModule::new(original, &compressor).await.unwrap();
+        let expanded = expander.expand_module(&module).await.unwrap();
+
+        assert_eq!(*original, expanded);
+    }
+
+    #[test]
+    async fn test_dictionary_mismatch_is_rejected() {
+        let samples_a = generate_samples(TEMPLATES, 500);
+        let dict_a = train_dictionary(&sample_refs(&samples_a), 512).unwrap();
+
+        let other_templates = ["Completely different vocabulary entirely unrelated to the first set."];
+        let samples_b = generate_samples(&other_templates, 500);
+        let dict_b = train_dictionary(&sample_refs(&samples_b), 512).unwrap();
+
+        let compressor = ZstdDictCompressor::new(&dict_a);
+        let expander = ZstdDictExpander::new(&dict_b);
+        let module = MemoryModule::new(&samples_a[0], &compressor).await.unwrap();
+
+        assert!(expander.expand_module(&module).await.is_err());
+    }
+
+    #[test]
+    async fn test_train_dictionary_fails_on_insufficient_samples() {
+        let result = train_dictionary(&["too small"], 1 << 20);
+        assert!(matches!(
+            result,
+            Err(StreamlinerError::DictionaryTrainingError(_))
+        ));
+    }
+}
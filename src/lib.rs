@@ -3,12 +3,18 @@
 //! Provides traits and implementations for compressing LLM context into memory modules
 //! that can be efficiently stored and expanded when needed.
 
+use std::collections::HashMap;
+use std::sync::Arc;
 use thiserror::Error;
 use serde::{Serialize, Deserialize};
 
 pub mod compression;
+pub mod storage;
 
-pub use compression::{ZlibCompressor, ZlibExpander};
+pub use compression::{
+    train_dictionary, Lz4Compressor, Lz4Expander, Lz4Mode, ZlibCompressor, ZlibExpander,
+    ZstdCompressor, ZstdDictCompressor, ZstdDictExpander, ZstdExpander,
+};
 
 /// Error type for compression/expansion operations
 #[derive(Error, Debug)]
@@ -19,6 +25,10 @@ pub enum StreamlinerError {
     ExpansionError(String),
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
+    #[error("Dictionary training failed: {0}")]
+    DictionaryTrainingError(String),
+    #[error("Unknown codec: {0}")]
+    UnknownCodec(String),
 }
 
 /// Trait for compressing text into binary representations
@@ -30,6 +40,16 @@ pub trait Compressor {
     /// # Returns
     /// Binary representation of the compressed text or error
     async fn compress(&'async_trait self, context: &'async_trait str) -> Result<Vec<u8>, StreamlinerError>;
+
+    /// Descriptor of the codec and parameters used, recorded in
+    /// `MemoryModule::metadata` at construction time so modules are
+    /// self-describing. Follows `"<algorithm>:v<version>[:params]"` (e.g.
+    /// `"zstd:v1:level=19"`); the algorithm segment is what
+    /// [`CodecRegistry`] matches against. Defaults to empty for backends
+    /// that predate this convention.
+    fn codec_tag(&self) -> String {
+        String::new()
+    }
 }
 
 /// Trait for expanding binary representations back into text
@@ -58,7 +78,7 @@ impl MemoryModule {
         let compressed_data = compressor.compress(context).await?;
         Ok(Self {
             compressed_data,
-            metadata: String::new(),
+            metadata: compressor.codec_tag(),
         })
     }
 
@@ -67,6 +87,18 @@ impl MemoryModule {
         expander.expand(&self.compressed_data).await
     }
 
+    /// Expands the module using whichever [`Expander`] `registry` has
+    /// registered for the algorithm recorded in [`MemoryModule::metadata`],
+    /// so the caller doesn't need out-of-band knowledge of which backend
+    /// produced the data. Fails with `StreamlinerError::UnknownCodec` if no
+    /// expander is registered for that algorithm.
+    pub async fn expand_auto(&self, registry: &CodecRegistry) -> Result<String, StreamlinerError> {
+        let expander = registry
+            .lookup(&self.metadata)
+            .ok_or_else(|| StreamlinerError::UnknownCodec(self.metadata.clone()))?;
+        expander.expand(&self.compressed_data).await
+    }
+
     /// Serializes the module to a JSON string
     pub fn to_json(&self) -> Result<String, StreamlinerError> {
         serde_json::to_string(self).map_err(Into::into)
@@ -77,6 +109,22 @@ impl MemoryModule {
         serde_json::from_str(json).map_err(Into::into)
     }
 
+    /// Constructs a module directly from already-compressed bytes and
+    /// metadata, without invoking a `Compressor`. Used by storage backends
+    /// that persist a module's raw bytes directly (e.g. the zip archive)
+    /// rather than round-tripping through JSON.
+    pub fn from_parts(compressed_data: Vec<u8>, metadata: String) -> Self {
+        Self {
+            compressed_data,
+            metadata,
+        }
+    }
+
+    /// The raw compressed bytes, for storage backends that persist them directly.
+    pub fn compressed_data(&self) -> &[u8] {
+        &self.compressed_data
+    }
+
     /// Gets metadata about the compression
     pub fn metadata(&self) -> &str {
         &self.metadata
@@ -88,6 +136,33 @@ impl MemoryModule {
     }
 }
 
+/// Maps a [`Compressor::codec_tag`] algorithm (e.g. `"zlib"`, `"zstd"`) to
+/// the [`Expander`] that can decode it, so stored modules can be expanded
+/// across deployments that support different backends without out-of-band
+/// knowledge of which one produced them.
+#[derive(Default)]
+pub struct CodecRegistry {
+    expanders: HashMap<String, Arc<dyn Expander + Send + Sync>>,
+}
+
+impl CodecRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `expander` to handle modules whose metadata's algorithm
+    /// segment (the part before the first `:`) matches `algorithm`.
+    pub fn register(&mut self, algorithm: &str, expander: impl Expander + Send + Sync + 'static) {
+        self.expanders.insert(algorithm.to_string(), Arc::new(expander));
+    }
+
+    fn lookup(&self, metadata: &str) -> Option<&Arc<dyn Expander + Send + Sync>> {
+        let algorithm = metadata.split(':').next().unwrap_or(metadata);
+        self.expanders.get(algorithm)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,10 +209,34 @@ mod tests {
         let compressor = TestCompressor;
         let expander = TestExpander;
         let original = "test context";
-        
+
         let module = MemoryModule::new(original, &compressor).await.unwrap();
         let expanded = module.expand(&expander).await.unwrap();
-        
+
         assert_eq!(original, expanded);
     }
+
+    #[test]
+    async fn test_expand_auto_selects_registered_codec() {
+        let original = "auto-detected context";
+        let module = MemoryModule::new(original, &ZstdCompressor::new()).await.unwrap();
+
+        let mut registry = CodecRegistry::new();
+        registry.register("zlib", ZlibExpander);
+        registry.register("zstd", ZstdExpander);
+
+        let expanded = module.expand_auto(&registry).await.unwrap();
+        assert_eq!(original, expanded);
+    }
+
+    #[test]
+    async fn test_expand_auto_rejects_unknown_codec() {
+        let original = "context with no registered expander";
+        let module = MemoryModule::new(original, &ZstdCompressor::new()).await.unwrap();
+
+        let registry = CodecRegistry::new();
+        let result = module.expand_auto(&registry).await;
+
+        assert!(matches!(result, Err(StreamlinerError::UnknownCodec(_))));
+    }
 }
@@ -0,0 +1,173 @@
+use super::StorageError;
+use crate::MemoryModule;
+use async_zip::tokio::read::seek::ZipFileReader;
+use async_zip::tokio::write::ZipFileWriter;
+use async_zip::{Compression, ZipEntryBuilder};
+use futures_lite::io::AsyncReadExt as _;
+use std::path::Path;
+use tokio::fs::File;
+use tokio::io::BufReader;
+
+/// Writes many [`MemoryModule`]s into a single random-access zip archive,
+/// one entry per module keyed by a logical name.
+///
+/// This lets a user keep a versioned library of context modules in one
+/// addressable file instead of thousands of loose `.dat` files, and the
+/// result interoperates with standard zip tooling.
+pub struct ModuleArchiveWriter {
+    writer: ZipFileWriter<File>,
+}
+
+impl ModuleArchiveWriter {
+    /// Creates a new archive at `path`, truncating any existing file.
+    pub async fn new(path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        let file = File::create(path).await?;
+        Ok(Self {
+            writer: ZipFileWriter::with_tokio(file),
+        })
+    }
+
+    /// Adds `module` to the archive under `name`. The entry holds the
+    /// module's raw compressed bytes directly (prefixed with its metadata),
+    /// not a JSON wrapper — JSON would serialize `compressed_data` as a
+    /// comma-separated array of decimal numbers, inflating it roughly 4x
+    /// over the already-compressed bytes it wraps. Since the module's own
+    /// compression is already applied, the entry itself uses
+    /// `Compression::Stored` rather than re-compressing it.
+    pub async fn add(&mut self, name: &str, module: &MemoryModule) -> Result<(), StorageError> {
+        let metadata_bytes = module.metadata().as_bytes();
+        let mut data =
+            Vec::with_capacity(4 + metadata_bytes.len() + module.compressed_data().len());
+        data.extend_from_slice(&(metadata_bytes.len() as u32).to_le_bytes());
+        data.extend_from_slice(metadata_bytes);
+        data.extend_from_slice(module.compressed_data());
+
+        let entry = ZipEntryBuilder::new(name.to_string().into(), Compression::Stored);
+        self.writer
+            .write_entry_whole(entry, &data)
+            .await
+            .map_err(|e| StorageError::Archive(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Finalizes the archive, flushing the central directory to disk.
+    pub async fn finish(self) -> Result<(), StorageError> {
+        self.writer
+            .close()
+            .await
+            .map_err(|e| StorageError::Archive(e.to_string()))?;
+        Ok(())
+    }
+}
+
+/// Reads individual [`MemoryModule`]s out of an archive written by
+/// [`ModuleArchiveWriter`], using seek-based reads so retrieving one module
+/// never requires decompressing the whole archive.
+pub struct ModuleArchiveReader {
+    reader: ZipFileReader<BufReader<File>>,
+}
+
+impl ModuleArchiveReader {
+    /// Opens an existing archive at `path`.
+    pub async fn open(path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        let file = BufReader::new(File::open(path).await?);
+        let reader = ZipFileReader::with_tokio(file)
+            .await
+            .map_err(|e| StorageError::Archive(e.to_string()))?;
+        Ok(Self { reader })
+    }
+
+    /// Lists the logical names of every module stored in the archive.
+    pub fn entry_names(&self) -> Vec<String> {
+        self.reader
+            .file()
+            .entries()
+            .iter()
+            .filter_map(|entry| entry.filename().as_str().ok().map(str::to_string))
+            .collect()
+    }
+
+    /// Retrieves a single module by its logical name, or `None` if no entry
+    /// with that name exists.
+    pub async fn get(&mut self, name: &str) -> Result<Option<MemoryModule>, StorageError> {
+        let index = self.reader.file().entries().iter().position(|entry| {
+            entry
+                .filename()
+                .as_str()
+                .map(|n| n == name)
+                .unwrap_or(false)
+        });
+        let Some(index) = index else {
+            return Ok(None);
+        };
+
+        let mut entry_reader = self
+            .reader
+            .reader_with_entry(index)
+            .await
+            .map_err(|e| StorageError::Archive(e.to_string()))?;
+        let mut data = Vec::new();
+        entry_reader
+            .read_to_end(&mut data)
+            .await
+            .map_err(StorageError::Io)?;
+
+        if data.len() < 4 {
+            return Err(StorageError::Archive(format!(
+                "entry {name:?} is too short to contain a metadata header"
+            )));
+        }
+        let metadata_len = u32::from_le_bytes(data[..4].try_into().unwrap()) as usize;
+        if data.len() < 4 + metadata_len {
+            return Err(StorageError::Archive(format!(
+                "entry {name:?} has a truncated metadata header"
+            )));
+        }
+        let metadata = String::from_utf8(data[4..4 + metadata_len].to_vec())
+            .map_err(|e| StorageError::Archive(e.to_string()))?;
+        let compressed_data = data[4 + metadata_len..].to_vec();
+
+        Ok(Some(MemoryModule::from_parts(compressed_data, metadata)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ZlibCompressor;
+    use tempfile::NamedTempFile;
+
+    #[tokio::test]
+    async fn test_archive_roundtrip() {
+        let compressor = ZlibCompressor;
+        let module_a = MemoryModule::new("first context module", &compressor)
+            .await
+            .unwrap();
+        let module_b = MemoryModule::new("second context module", &compressor)
+            .await
+            .unwrap();
+
+        let temp_file = NamedTempFile::new().unwrap();
+        let path = temp_file.path().to_path_buf();
+
+        let mut writer = ModuleArchiveWriter::new(&path).await.unwrap();
+        writer.add("system-prompt", &module_a).await.unwrap();
+        writer.add("few-shot-examples", &module_b).await.unwrap();
+        writer.finish().await.unwrap();
+
+        let mut reader = ModuleArchiveReader::open(&path).await.unwrap();
+        assert_eq!(
+            reader.entry_names(),
+            vec!["system-prompt".to_string(), "few-shot-examples".to_string()]
+        );
+
+        let expander = crate::ZlibExpander;
+        let fetched = reader.get("few-shot-examples").await.unwrap().unwrap();
+        assert_eq!(
+            fetched.expand(&expander).await.unwrap(),
+            "second context module"
+        );
+
+        assert!(reader.get("missing").await.unwrap().is_none());
+    }
+}
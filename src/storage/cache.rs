@@ -0,0 +1,341 @@
+use super::StorageError;
+use crate::{Compressor, MemoryModule};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash as StdHash, Hasher};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tokio::fs;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Content hash identifying a cached module, derived from its decompressed
+/// context so that re-streamlining identical text is a cache hit rather
+/// than a fresh compression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, StdHash)]
+pub struct Hash(u64);
+
+impl Hash {
+    fn of(context: &str) -> Self {
+        let mut hasher = DefaultHasher::new();
+        context.hash(&mut hasher);
+        Self(hasher.finish())
+    }
+
+    fn from_hex(s: &str) -> Option<Self> {
+        u64::from_str_radix(s, 16).ok().map(Self)
+    }
+}
+
+impl std::fmt::Display for Hash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+/// Bounds applied to a [`CacheStore`] on every `put`. `None` disables that
+/// bound entirely.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EvictionPolicy {
+    /// Total size in bytes the cache directory may hold; oldest entries are
+    /// evicted first once exceeded.
+    pub max_total_bytes: Option<u64>,
+    /// Maximum age an entry may reach before it's evicted regardless of
+    /// total size.
+    pub max_age: Option<Duration>,
+}
+
+/// Content-addressed cache of compressed [`MemoryModule`]s, keyed by a hash
+/// of the original (decompressed) context. Avoids re-compressing identical
+/// contexts — e.g. the same system prompt streamlined repeatedly — and
+/// bounds disk growth via `EvictionPolicy`.
+pub struct CacheStore {
+    dir: PathBuf,
+    policy: EvictionPolicy,
+}
+
+impl CacheStore {
+    /// Opens (or creates) a cache store rooted at `dir` with no eviction bounds.
+    pub async fn new(dir: impl Into<PathBuf>) -> Result<Self, StorageError> {
+        Self::with_policy(dir, EvictionPolicy::default()).await
+    }
+
+    /// Opens (or creates) a cache store rooted at `dir`, applying `policy` on every `put`.
+    pub async fn with_policy(
+        dir: impl Into<PathBuf>,
+        policy: EvictionPolicy,
+    ) -> Result<Self, StorageError> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir).await?;
+        Ok(Self { dir, policy })
+    }
+
+    fn path_for(&self, hash: Hash) -> PathBuf {
+        self.dir.join(format!("{hash}.module"))
+    }
+
+    /// Stores `module` under a key derived from `context`, tags the module's
+    /// metadata with the hash and original byte length (see
+    /// [`CacheStore::verify`]), applies the eviction policy, and returns the
+    /// key.
+    pub async fn put(&self, context: &str, mut module: MemoryModule) -> Result<Hash, StorageError> {
+        let hash = Hash::of(context);
+        module.set_metadata(format!(
+            "{};hash={hash};len={}",
+            module.metadata(),
+            context.len()
+        ));
+
+        let path = self.path_for(hash);
+        let mut file = fs::File::create(&path).await?;
+        file.write_all(module.to_json()?.as_bytes()).await?;
+
+        self.evict(&path).await?;
+        Ok(hash)
+    }
+
+    /// Returns the cached module for `context` if present, otherwise
+    /// compresses it with `compressor` and stores the result. Unlike `put`,
+    /// this is what actually avoids re-compressing identical contexts: the
+    /// hash is checked *before* paying the compression cost, not after.
+    pub async fn get_or_insert(
+        &self,
+        context: &str,
+        compressor: &impl Compressor,
+    ) -> Result<MemoryModule, StorageError> {
+        let hash = Hash::of(context);
+        if let Some(module) = self.get(hash).await? {
+            return Ok(module);
+        }
+
+        let module = MemoryModule::new(context, compressor).await?;
+        self.put(context, module).await?;
+        Ok(self
+            .get(hash)
+            .await?
+            .expect("module was just written by put"))
+    }
+
+    /// Checks `expanded` — the result of calling `module.expand(...)` or
+    /// `expand_auto(...)` — against the hash and original byte length
+    /// `put` recorded in `module`'s metadata, catching silent corruption
+    /// between storing and expanding a module. Returns `Ok(false)` (not an
+    /// error) for a module that predates cache tagging and so carries no
+    /// `hash=`/`len=` fields.
+    pub fn verify(module: &MemoryModule, expanded: &str) -> bool {
+        match parse_cache_tag(module.metadata()) {
+            Some((hash, len)) => hash == Hash::of(expanded) && len == expanded.len(),
+            None => false,
+        }
+    }
+
+    /// Retrieves the module stored under `hash`, or `None` if absent.
+    pub async fn get(&self, hash: Hash) -> Result<Option<MemoryModule>, StorageError> {
+        let path = self.path_for(hash);
+        let mut file = match fs::File::open(&path).await {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut json = String::new();
+        file.read_to_string(&mut json).await?;
+        Ok(Some(MemoryModule::from_json(&json)?))
+    }
+
+    /// Applies the eviction policy, never removing `protect` — the entry
+    /// `put` just wrote. Without this, a size budget smaller than a single
+    /// entry would have the size-based pass below delete that entry inside
+    /// the very `put` call that created it, leaving callers like
+    /// `get_or_insert` unable to read back what they just stored.
+    async fn evict(&self, protect: &Path) -> Result<(), StorageError> {
+        let mut entries = Vec::new();
+        let mut dir = fs::read_dir(&self.dir).await?;
+        while let Some(entry) = dir.next_entry().await? {
+            let metadata = entry.metadata().await?;
+            if !metadata.is_file() {
+                continue;
+            }
+            let modified = metadata.modified()?;
+            entries.push((entry.path(), metadata.len(), modified));
+        }
+
+        if let Some(max_age) = self.policy.max_age {
+            let now = SystemTime::now();
+            let is_expired = |modified: SystemTime| {
+                now.duration_since(modified).unwrap_or(Duration::ZERO) > max_age
+            };
+            for (path, _, modified) in &entries {
+                if path != protect && is_expired(*modified) {
+                    remove_file_ignoring_missing(path).await?;
+                }
+            }
+            entries.retain(|(path, _, modified)| path == protect || !is_expired(*modified));
+        }
+
+        if let Some(max_total_bytes) = self.policy.max_total_bytes {
+            let mut total: u64 = entries.iter().map(|(_, len, _)| len).sum();
+            let mut evictable: Vec<_> = entries.iter().filter(|(path, _, _)| path != protect).collect();
+            evictable.sort_by_key(|(_, _, modified)| *modified);
+            for (path, len, _) in evictable {
+                if total <= max_total_bytes {
+                    break;
+                }
+                remove_file_ignoring_missing(path).await?;
+                total = total.saturating_sub(*len);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_cache_tag(metadata: &str) -> Option<(Hash, usize)> {
+    let hash = metadata
+        .split(';')
+        .find_map(|part| part.strip_prefix("hash="))
+        .and_then(Hash::from_hex)?;
+    let len = metadata
+        .split(';')
+        .find_map(|part| part.strip_prefix("len="))
+        .and_then(|s| s.parse().ok())?;
+    Some((hash, len))
+}
+
+async fn remove_file_ignoring_missing(path: &Path) -> Result<(), StorageError> {
+    match fs::remove_file(path).await {
+        Ok(()) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ZlibCompressor;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_cache_roundtrip() {
+        let dir = TempDir::new().unwrap();
+        let cache = CacheStore::new(dir.path()).await.unwrap();
+        let compressor = ZlibCompressor;
+        let context = "a system prompt compressed and cached";
+        let module = MemoryModule::new(context, &compressor).await.unwrap();
+
+        let hash = cache.put(context, module).await.unwrap();
+        let cached = cache.get(hash).await.unwrap().unwrap();
+
+        assert!(cached.metadata().contains(&format!("hash={hash}")));
+        assert!(cached.metadata().contains(&format!("len={}", context.len())));
+
+        let expander = crate::ZlibExpander;
+        let expanded = cached.expand(&expander).await.unwrap();
+        assert!(CacheStore::verify(&cached, &expanded));
+        assert!(!CacheStore::verify(&cached, "not the original context"));
+    }
+
+    #[tokio::test]
+    async fn test_get_or_insert_compresses_only_on_first_call() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        struct CountingCompressor {
+            inner: ZlibCompressor,
+            calls: AtomicUsize,
+        }
+
+        #[async_trait::async_trait]
+        impl Compressor for CountingCompressor {
+            async fn compress(
+                &'async_trait self,
+                context: &'async_trait str,
+            ) -> Result<Vec<u8>, crate::StreamlinerError> {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                self.inner.compress(context).await
+            }
+        }
+
+        let dir = TempDir::new().unwrap();
+        let cache = CacheStore::new(dir.path()).await.unwrap();
+        let compressor = CountingCompressor {
+            inner: ZlibCompressor,
+            calls: AtomicUsize::new(0),
+        };
+        let context = "a system prompt compressed and cached only once";
+
+        cache.get_or_insert(context, &compressor).await.unwrap();
+        cache.get_or_insert(context, &compressor).await.unwrap();
+
+        assert_eq!(compressor.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cache_miss_returns_none() {
+        let dir = TempDir::new().unwrap();
+        let cache = CacheStore::new(dir.path()).await.unwrap();
+
+        assert!(cache.get(Hash::of("never stored")).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cache_evicts_oldest_entry_over_size_budget() {
+        let dir = TempDir::new().unwrap();
+        let compressor = ZlibCompressor;
+
+        // Insert the first entry with no eviction bound, then measure its
+        // on-disk size (post-tagging) so the budget below fits exactly one
+        // entry. If eviction picked the wrong one (newest instead of
+        // oldest) this test would catch it.
+        let unbounded_cache = CacheStore::new(dir.path()).await.unwrap();
+        // Same length and similarly-structured text so the two entries
+        // serialize to nearly identical sizes on disk.
+        let first_context = "the first cached context, thirty chars";
+        let second_context = "the second cached context, thirty chars";
+        let first_module = MemoryModule::new(first_context, &compressor).await.unwrap();
+        let first_hash = unbounded_cache.put(first_context, first_module).await.unwrap();
+        let first_entry_size = fs::metadata(unbounded_cache.path_for(first_hash))
+            .await
+            .unwrap()
+            .len();
+
+        let cache = CacheStore::with_policy(
+            dir.path(),
+            EvictionPolicy {
+                max_total_bytes: Some(first_entry_size + 64),
+                max_age: None,
+            },
+        )
+        .await
+        .unwrap();
+
+        let second_module = MemoryModule::new(second_context, &compressor).await.unwrap();
+        let second_hash = cache.put(second_context, second_module).await.unwrap();
+
+        assert!(cache.get(first_hash).await.unwrap().is_none());
+        assert!(cache.get(second_hash).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_get_or_insert_survives_budget_smaller_than_one_entry() {
+        let dir = TempDir::new().unwrap();
+        let cache = CacheStore::with_policy(
+            dir.path(),
+            EvictionPolicy {
+                max_total_bytes: Some(1),
+                max_age: None,
+            },
+        )
+        .await
+        .unwrap();
+        let compressor = ZlibCompressor;
+
+        let module = cache
+            .get_or_insert("a context larger than the tiny byte budget", &compressor)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            module.expand(&crate::ZlibExpander).await.unwrap(),
+            "a context larger than the tiny byte budget"
+        );
+    }
+}
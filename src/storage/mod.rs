@@ -28,12 +28,22 @@ use thiserror::Error;
 use tokio::fs;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
+mod archive;
+mod cache;
+
+pub use archive::{ModuleArchiveReader, ModuleArchiveWriter};
+pub use cache::{CacheStore, EvictionPolicy, Hash};
+
 #[derive(Error, Debug)]
 pub enum StorageError {
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
+    #[error("Module error: {0}")]
+    Module(#[from] crate::StreamlinerError),
+    #[error("Archive error: {0}")]
+    Archive(String),
 }
 
 /// Stores compressed memory modules to disk